@@ -1,26 +1,252 @@
+use std::any::{Any, TypeId};
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
 
+use futures::channel::oneshot;
 use yew::prelude::*;
 
 use crate::*;
 
+/// Implemented by a worker's [`Input`](Bridged::Input) and [`Output`](Bridged::Output)
+/// types to support request/response correlation via
+/// [`UseBridgeHandle::send_request`].
+///
+/// An `Output` produced in reply to a `send_request` call must report the same id
+/// that [`with_request_id`](Correlated::with_request_id) attached to the
+/// originating `Input`, so [`UseBridgeHandle`]'s output dispatcher can route it
+/// back to the right pending request.
+///
+/// Implementing this trait is entirely optional: [`use_bridge`] and
+/// [`use_bridge_shared`] accept any [`Bridged`] worker, correlated or not, and
+/// [`send_request`](UseBridgeHandle::send_request) is only available once
+/// `Input`/`Output` opt in.
+pub trait Correlated {
+    /// Returns `self` tagged with `id`.
+    fn with_request_id(self, id: usize) -> Self;
+
+    /// Returns the request id this message carries, if any.
+    fn request_id(&self) -> Option<usize>;
+}
+
+/// Reads a message's [`Correlated::request_id`] when its type implements
+/// [`Correlated`], or `None` otherwise — relies on inherent methods shadowing
+/// trait methods, so callers never need a `Correlated` bound.
+struct Probe<'a, M>(&'a M);
+
+trait ProbeFallback {
+    fn probe_request_id(&self) -> Option<usize>;
+}
+
+impl<M> ProbeFallback for Probe<'_, M> {
+    fn probe_request_id(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<M: Correlated> Probe<'_, M> {
+    fn probe_request_id(&self) -> Option<usize> {
+        self.0.request_id()
+    }
+}
+
+/// Error returned when a bridge is accessed reentrantly, e.g. from inside its own
+/// `on_output` callback while the worker replied synchronously.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeBusy;
+
+impl fmt::Display for BridgeBusy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("bridge is already borrowed and cannot be accessed reentrantly")
+    }
+}
+
+impl std::error::Error for BridgeBusy {}
+
 /// State handle for [`use_bridge`] hook
 pub struct UseBridgeHandle<T>
 where
     T: Bridged,
 {
-    inner: Rc<RefCell<Box<dyn Bridge<T>>>>,
+    inner: Rc<RefCell<Option<Box<dyn Bridge<T>>>>>,
+
+    // Only `Some` for handles returned by [`use_bridge_shared`]. Keeps the shared
+    // bridge's reference count in sync with how many handles point at it; dropping
+    // the last one tears the bridge down.
+    _shared_guard: Option<Rc<SharedBridgeGuard<T>>>,
+
+    next_request_id: Rc<AtomicUsize>,
+    pending: Rc<RefCell<HashMap<usize, oneshot::Sender<T::Output>>>>,
+}
+
+/// Puts a bridge taken out of a [`UseBridgeHandle`]'s cell back when dropped,
+/// including on panic, so the cell never stays empty forever.
+struct RestoreOnDrop<'a, T>
+where
+    T: Bridged,
+{
+    cell: &'a Rc<RefCell<Option<Box<dyn Bridge<T>>>>>,
+    bridge: Option<Box<dyn Bridge<T>>>,
+}
+
+impl<T> Drop for RestoreOnDrop<'_, T>
+where
+    T: Bridged,
+{
+    fn drop(&mut self) {
+        *self.cell.borrow_mut() = self.bridge.take();
+    }
 }
 
 impl<T> UseBridgeHandle<T>
 where
     T: Bridged,
 {
+    /// Runs `f` with exclusive, scoped access to the underlying bridge.
+    ///
+    /// The bridge is taken out of its cell for the duration of `f` and put back
+    /// immediately after, rather than held borrowed across the whole call, so a
+    /// reentrant call from within `f` gets [`BridgeBusy`] instead of a `RefCell`
+    /// already-borrowed panic. Restored even if `f` panics.
+    fn with_bridge<R>(&self, f: impl FnOnce(&mut Box<dyn Bridge<T>>) -> R) -> Result<R, BridgeBusy> {
+        let bridge = self.inner.borrow_mut().take().ok_or(BridgeBusy)?;
+        let mut restore = RestoreOnDrop {
+            cell: &self.inner,
+            bridge: Some(bridge),
+        };
+
+        Ok(f(restore.bridge.as_mut().expect("bridge taken out of the guard")))
+    }
+
     /// Send a message to an worker.
-    pub fn send(&self, msg: T::Input) {
-        let mut bridge = self.inner.borrow_mut();
-        bridge.send(msg);
+    ///
+    /// Returns [`BridgeBusy`] if called reentrantly, e.g. from within the
+    /// `on_output` callback while handling a synchronous reply.
+    pub fn send(&self, msg: T::Input) -> Result<(), BridgeBusy> {
+        self.with_bridge(|bridge| bridge.send(msg))
+    }
+}
+
+impl<T> UseBridgeHandle<T>
+where
+    T: Bridged,
+    T::Input: Correlated,
+    T::Output: Correlated,
+{
+    /// Sends `msg` and returns a future that resolves with the
+    /// [`Output`](Bridged::Output) correlated to it, instead of delivering the
+    /// reply out-of-band through `on_output`.
+    ///
+    /// Each call is tagged with a fresh, monotonically-increasing request id via
+    /// [`Correlated::with_request_id`]. The handle's output dispatcher checks
+    /// every incoming [`Output`](Bridged::Output) for a matching
+    /// [`Correlated::request_id`] and, when found, resolves the corresponding
+    /// future instead of forwarding it to `on_output`; unmatched output is passed
+    /// through to `on_output` as before.
+    ///
+    /// Returns [`BridgeBusy`] without registering a pending reply if the
+    /// underlying [`send`](Self::send) fails, e.g. when called reentrantly from
+    /// an `on_output` callback — otherwise the returned future would await a
+    /// reply to a request that was never actually sent.
+    ///
+    /// The returned future resolves to [`Err(oneshot::Canceled)`](oneshot::Canceled)
+    /// if the bridge is torn down (e.g. the component unmounts, or the last
+    /// `use_bridge_shared` subscriber drops) before a correlated reply arrives,
+    /// rather than panicking — that's a normal outcome of a pending request
+    /// outliving its bridge, not a bug.
+    pub fn send_request(
+        &self,
+        msg: T::Input,
+    ) -> Result<impl Future<Output = Result<T::Output, oneshot::Canceled>>, BridgeBusy> {
+        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.borrow_mut().insert(id, tx);
+
+        if let Err(err) = self.send(msg.with_request_id(id)) {
+            self.pending.borrow_mut().remove(&id);
+            return Err(err);
+        }
+
+        Ok(PendingReply {
+            id,
+            pending: Rc::downgrade(&self.pending),
+            rx,
+        })
+    }
+}
+
+/// Future returned by [`send_request`](UseBridgeHandle::send_request). If dropped
+/// before a reply is claimed, removes its id from `pending` so an abandoned
+/// request (e.g. the component unmounted) doesn't leak a sender forever.
+struct PendingReply<T>
+where
+    T: Bridged,
+{
+    id: usize,
+    // Weak, so a pending reply does not keep its own sender alive once the
+    // bridge and its handles are gone.
+    pending: std::rc::Weak<RefCell<HashMap<usize, oneshot::Sender<T::Output>>>>,
+    rx: oneshot::Receiver<T::Output>,
+}
+
+impl<T> Future for PendingReply<T>
+where
+    T: Bridged,
+{
+    type Output = Result<T::Output, oneshot::Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx).poll(cx)
+    }
+}
+
+impl<T> Drop for PendingReply<T>
+where
+    T: Bridged,
+{
+    fn drop(&mut self) {
+        if let Some(pending) = self.pending.upgrade() {
+            pending.borrow_mut().remove(&self.id);
+        }
+    }
+}
+
+/// Claims `output` for its matching pending [`send_request`](UseBridgeHandle::send_request)
+/// reply, returning `None` once claimed, or `output` back to the caller otherwise.
+fn claim_correlated_reply<T>(
+    output: T::Output,
+    pending: &Rc<RefCell<HashMap<usize, oneshot::Sender<T::Output>>>>,
+) -> Option<T::Output>
+where
+    T: Bridged,
+{
+    let id = Probe(&output).probe_request_id()?;
+
+    match pending.borrow_mut().remove(&id) {
+        Some(tx) => {
+            let _ = tx.send(output);
+            None
+        }
+        None => Some(output),
+    }
+}
+
+/// Routes a correlated reply to its pending [`send_request`](UseBridgeHandle::send_request)
+/// future and forwards everything else to `on_output`.
+fn dispatch_output<T>(
+    output: T::Output,
+    pending: &Rc<RefCell<HashMap<usize, oneshot::Sender<T::Output>>>>,
+    on_output: &dyn Fn(T::Output),
+) where
+    T: Bridged,
+{
+    if let Some(output) = claim_correlated_reply::<T>(output, pending) {
+        on_output(output);
     }
 }
 
@@ -76,22 +302,466 @@ where
         *on_output_ref = on_output;
     }
 
+    let pending = use_mut_ref(HashMap::new);
+    let next_request_id = use_mut_ref(|| Rc::new(AtomicUsize::new(0)))
+        .borrow()
+        .clone();
+
+    let pending_dispatch = pending.clone();
     let bridge = use_mut_ref(move || {
-        T::bridge({
+        Some(T::bridge({
             Rc::new(move |output| {
                 let on_output = on_output_ref.borrow().clone();
-                on_output(output);
+                dispatch_output::<T>(output, &pending_dispatch, &*on_output);
             })
-        })
+        }))
     });
 
-    UseBridgeHandle { inner: bridge }
+    UseBridgeHandle {
+        inner: bridge,
+        _shared_guard: None,
+        next_request_id,
+        pending,
+    }
 }
 
 impl<T: Worker> Clone for UseBridgeHandle<T> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            _shared_guard: self._shared_guard.clone(),
+            next_request_id: self.next_request_id.clone(),
+            pending: self.pending.clone(),
         }
     }
 }
+
+/// The state backing every [`use_bridge_shared`] handle for one worker type: the
+/// single live bridge, its subscriber callbacks, and the correlation bookkeeping
+/// shared across every subscriber's [`send_request`](UseBridgeHandle::send_request)
+/// calls.
+struct SharedBridgeState<T>
+where
+    T: Bridged,
+{
+    bridge: Rc<RefCell<Option<Box<dyn Bridge<T>>>>>,
+    subscribers: Rc<RefCell<HashMap<usize, Rc<RefCell<Rc<dyn Fn(T::Output)>>>>>>,
+    next_subscriber_id: Rc<RefCell<usize>>,
+    next_request_id: Rc<AtomicUsize>,
+    pending: Rc<RefCell<HashMap<usize, oneshot::Sender<T::Output>>>>,
+}
+
+/// A single entry in the process-wide shared-bridge registry: one live bridge plus
+/// the subscriber count keeping it alive, interned behind its worker's [`TypeId`].
+struct SharedBridgeEntry {
+    state: Rc<dyn Any>,
+    count: Rc<AtomicUsize>,
+}
+
+thread_local! {
+    static SHARED_BRIDGES: RefCell<HashMap<TypeId, SharedBridgeEntry>> = RefCell::new(HashMap::new());
+}
+
+/// Unsubscribes a shared bridge handle and, once the last subscriber drops,
+/// terminates the worker and evicts it from [`SHARED_BRIDGES`].
+struct SharedBridgeGuard<T>
+where
+    T: Bridged,
+{
+    ty: TypeId,
+    subscriber_id: usize,
+    subscribers: Rc<RefCell<HashMap<usize, Rc<RefCell<Rc<dyn Fn(T::Output)>>>>>>,
+    count: Rc<AtomicUsize>,
+}
+
+impl<T> SharedBridgeGuard<T>
+where
+    T: Bridged,
+{
+    fn drop(&mut self) {
+        self.subscribers.borrow_mut().remove(&self.subscriber_id);
+
+        if self.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            SHARED_BRIDGES.with(|registry| {
+                registry.borrow_mut().remove(&self.ty);
+            });
+        }
+    }
+}
+
+impl<T> Drop for SharedBridgeGuard<T>
+where
+    T: Bridged,
+{
+    fn drop(&mut self) {
+        SharedBridgeGuard::drop(self)
+    }
+}
+
+/// A hook to bridge to a [`Worker`], sharing a single underlying bridge across
+/// every component that calls it for the same worker type `T`.
+///
+/// Unlike [`use_bridge`], which spins up a fresh [`Bridge<T>`] per call site,
+/// `use_bridge_shared` looks up `T` in a process-global registry keyed by
+/// [`TypeId`]. The first caller creates the bridge; every later caller reuses it
+/// and just registers its own `on_output` callback, so every subscriber still
+/// sees every output. The worker is terminated and its entry evicted from the
+/// registry once the last [`UseBridgeHandle`] pointing at it is dropped.
+///
+/// A reply correlated to a [`send_request`](UseBridgeHandle::send_request) call
+/// is routed to its requester *before* output is fanned out to subscribers, so
+/// only the component that made the request ever sees it; every other
+/// subscriber's `on_output` is not called for it at all.
+///
+/// # Examples
+///
+/// ```
+/// use yew::prelude::*;
+/// use yew_agent::use_bridge_shared;
+///
+/// #[function_component(UseBridgeShared)]
+/// fn bridge_shared() -> Html {
+///     let counter = use_state(|| 0);
+///
+///     {
+///         let counter = counter.clone();
+///         let bridge = use_bridge_shared(move |response| match response {
+///             WorkerResponseType::IncrementCounter => {
+///                 counter.set(*counter + 1);
+///             }
+///         });
+///     }
+///
+///     html! {
+///         <div>
+///             {*counter}
+///         </div>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_bridge_shared<T, F>(on_output: F) -> UseBridgeHandle<T>
+where
+    T: Bridged,
+    T::Output: Clone,
+    F: Fn(T::Output) + 'static,
+{
+    let on_output: Rc<dyn Fn(T::Output)> = Rc::new(on_output);
+
+    let on_output_clone = on_output.clone();
+    let on_output_ref = use_mut_ref(move || on_output_clone);
+
+    // Refresh the callback on every render.
+    {
+        let mut on_output_ref = on_output_ref.borrow_mut();
+        *on_output_ref = on_output;
+    }
+
+    let (bridge, guard, next_request_id, pending) = use_mut_ref(move || {
+        let ty = TypeId::of::<T>();
+
+        let state = SHARED_BRIDGES.with(|registry| {
+            let mut registry = registry.borrow_mut();
+
+            let entry = registry.entry(ty).or_insert_with(|| {
+                let subscribers: Rc<RefCell<HashMap<usize, Rc<RefCell<Rc<dyn Fn(T::Output)>>>>>> =
+                    Rc::new(RefCell::new(HashMap::new()));
+                let pending: Rc<RefCell<HashMap<usize, oneshot::Sender<T::Output>>>> =
+                    Rc::new(RefCell::new(HashMap::new()));
+
+                let dispatch_subscribers = subscribers.clone();
+                let dispatch_pending = pending.clone();
+                let bridge = Rc::new(RefCell::new(Some(T::bridge(Rc::new(
+                    move |output: T::Output| {
+                        let output = match claim_correlated_reply::<T>(output, &dispatch_pending) {
+                            Some(output) => output,
+                            None => return,
+                        };
+
+                        for on_output in dispatch_subscribers.borrow().values() {
+                            let on_output = on_output.borrow().clone();
+                            on_output(output.clone());
+                        }
+                    },
+                )))));
+
+                SharedBridgeEntry {
+                    state: Rc::new(SharedBridgeState {
+                        bridge,
+                        subscribers,
+                        next_subscriber_id: Rc::new(RefCell::new(0)),
+                        next_request_id: Rc::new(AtomicUsize::new(0)),
+                        pending,
+                    }),
+                    count: Rc::new(AtomicUsize::new(0)),
+                }
+            });
+
+            entry.count.fetch_add(1, Ordering::AcqRel);
+
+            (
+                entry
+                    .state
+                    .clone()
+                    .downcast::<SharedBridgeState<T>>()
+                    .expect("bridge registered under the wrong TypeId"),
+                entry.count.clone(),
+            )
+        });
+
+        let (state, count) = state;
+
+        let subscriber_id = {
+            let mut next_id = state.next_subscriber_id.borrow_mut();
+            let id = *next_id;
+            *next_id += 1;
+            id
+        };
+        state
+            .subscribers
+            .borrow_mut()
+            .insert(subscriber_id, on_output_ref.clone());
+
+        (
+            state.bridge.clone(),
+            Rc::new(SharedBridgeGuard {
+                ty,
+                subscriber_id,
+                subscribers: state.subscribers.clone(),
+                count,
+            }),
+            state.next_request_id.clone(),
+            state.pending.clone(),
+        )
+    })
+    .borrow()
+    .clone();
+
+    UseBridgeHandle {
+        inner: bridge,
+        _shared_guard: Some(guard),
+        next_request_id,
+        pending,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct TestInput(u32);
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct TestOutput {
+        id: Option<usize>,
+        value: u32,
+    }
+
+    impl Correlated for TestOutput {
+        fn with_request_id(mut self, id: usize) -> Self {
+            self.id = Some(id);
+            self
+        }
+
+        fn request_id(&self) -> Option<usize> {
+            self.id
+        }
+    }
+
+    struct TestWorker;
+
+    impl Worker for TestWorker {
+        type Message = ();
+        type Input = TestInput;
+        type Output = TestOutput;
+    }
+
+    /// Echoes every sent [`TestInput`] straight back through the registered
+    /// callback, synchronously, so tests can exercise reentrant `on_output` calls.
+    struct EchoBridge {
+        callback: Rc<dyn Fn(TestOutput)>,
+    }
+
+    impl Bridge<TestWorker> for EchoBridge {
+        fn send(&mut self, msg: TestInput) {
+            (self.callback)(TestOutput {
+                id: None,
+                value: msg.0,
+            });
+        }
+    }
+
+    impl Bridged for TestWorker {
+        fn bridge(callback: Rc<dyn Fn(TestOutput)>) -> Box<dyn Bridge<Self>> {
+            Box::new(EchoBridge { callback })
+        }
+    }
+
+    // chunk0-1: a shared bridge's registry entry is evicted exactly once the
+    // ref count drops to zero, not before.
+
+    #[test]
+    fn shared_bridge_guard_evicts_entry_once_last_subscriber_drops() {
+        let ty = TypeId::of::<TestWorker>();
+        let subscribers: Rc<RefCell<HashMap<usize, Rc<RefCell<Rc<dyn Fn(TestOutput)>>>>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let count = Rc::new(AtomicUsize::new(2));
+
+        subscribers.borrow_mut().insert(
+            0,
+            Rc::new(RefCell::new(Rc::new(|_: TestOutput| {}) as Rc<dyn Fn(TestOutput)>)),
+        );
+        subscribers.borrow_mut().insert(
+            1,
+            Rc::new(RefCell::new(Rc::new(|_: TestOutput| {}) as Rc<dyn Fn(TestOutput)>)),
+        );
+
+        SHARED_BRIDGES.with(|registry| {
+            registry.borrow_mut().insert(
+                ty,
+                SharedBridgeEntry {
+                    state: Rc::new(()),
+                    count: count.clone(),
+                },
+            );
+        });
+
+        let first = SharedBridgeGuard::<TestWorker> {
+            ty,
+            subscriber_id: 0,
+            subscribers: subscribers.clone(),
+            count: count.clone(),
+        };
+        let second = SharedBridgeGuard::<TestWorker> {
+            ty,
+            subscriber_id: 1,
+            subscribers: subscribers.clone(),
+            count: count.clone(),
+        };
+
+        drop(first);
+        assert_eq!(subscribers.borrow().len(), 1);
+        assert!(SHARED_BRIDGES.with(|registry| registry.borrow().contains_key(&ty)));
+
+        drop(second);
+        assert_eq!(subscribers.borrow().len(), 0);
+        assert!(!SHARED_BRIDGES.with(|registry| registry.borrow().contains_key(&ty)));
+    }
+
+    // chunk0-2: reentrant access reports `BridgeBusy` instead of panicking, and
+    // the handle recovers so later calls work normally.
+
+    fn handle_with_callback<F>(on_output: F) -> UseBridgeHandle<TestWorker>
+    where
+        F: Fn(TestOutput) + 'static,
+    {
+        let on_output: Rc<dyn Fn(TestOutput)> = Rc::new(on_output);
+        let pending = Rc::new(RefCell::new(HashMap::new()));
+        let next_request_id = Rc::new(AtomicUsize::new(0));
+
+        let dispatch_pending = pending.clone();
+        let bridge = Rc::new(RefCell::new(Some(TestWorker::bridge(Rc::new(
+            move |output| dispatch_output::<TestWorker>(output, &dispatch_pending, &*on_output),
+        )))));
+
+        UseBridgeHandle {
+            inner: bridge,
+            _shared_guard: None,
+            next_request_id,
+            pending,
+        }
+    }
+
+    #[test]
+    fn send_reports_bridge_busy_on_reentrant_call() {
+        let handle_cell: Rc<RefCell<Option<UseBridgeHandle<TestWorker>>>> =
+            Rc::new(RefCell::new(None));
+        let reentrant_result = Rc::new(RefCell::new(None));
+
+        let handle_cell_for_callback = handle_cell.clone();
+        let reentrant_result_for_callback = reentrant_result.clone();
+        let handle = handle_with_callback(move |_output| {
+            let handle = handle_cell_for_callback.borrow();
+            let handle = handle.as_ref().expect("handle installed before first send");
+            *reentrant_result_for_callback.borrow_mut() = Some(handle.send(TestInput(1)));
+        });
+        *handle_cell.borrow_mut() = Some(handle.clone());
+
+        assert_eq!(handle.send(TestInput(0)), Ok(()));
+        assert_eq!(*reentrant_result.borrow(), Some(Err(BridgeBusy)));
+    }
+
+    #[test]
+    fn with_bridge_recovers_after_a_panicking_call() {
+        let handle = handle_with_callback(|_output| {});
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            handle.with_bridge(|_bridge| panic!("boom"))
+        }));
+        assert!(panicked.is_err());
+
+        // The bridge was put back despite the panic, so later calls work.
+        assert_eq!(handle.send(TestInput(1)), Ok(()));
+    }
+
+    // chunk0-3: correlated replies are claimed by their pending request and never
+    // reach any subscriber's `on_output`.
+
+    #[test]
+    fn claim_correlated_reply_resolves_the_matching_pending_request() {
+        let pending: Rc<RefCell<HashMap<usize, oneshot::Sender<TestOutput>>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let (tx, mut rx) = oneshot::channel();
+        pending.borrow_mut().insert(7, tx);
+
+        let reply = TestOutput {
+            id: Some(7),
+            value: 42,
+        };
+        let claimed = claim_correlated_reply::<TestWorker>(reply.clone(), &pending);
+
+        assert_eq!(claimed, None);
+        assert!(pending.borrow().is_empty());
+        assert_eq!(rx.try_recv(), Ok(Some(reply)));
+    }
+
+    #[test]
+    fn claim_correlated_reply_passes_through_unmatched_output() {
+        let pending: Rc<RefCell<HashMap<usize, oneshot::Sender<TestOutput>>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let output = TestOutput { id: None, value: 1 };
+
+        assert_eq!(
+            claim_correlated_reply::<TestWorker>(output.clone(), &pending),
+            Some(output)
+        );
+    }
+
+    #[test]
+    fn correlated_reply_never_reaches_other_subscribers() {
+        let pending: Rc<RefCell<HashMap<usize, oneshot::Sender<TestOutput>>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let (tx, mut rx) = oneshot::channel();
+        pending.borrow_mut().insert(1, tx);
+
+        let other_subscriber_calls: Rc<RefCell<Vec<TestOutput>>> =
+            Rc::new(RefCell::new(Vec::new()));
+        let calls = other_subscriber_calls.clone();
+        let subscribers: Vec<Rc<dyn Fn(TestOutput)>> =
+            vec![Rc::new(move |output: TestOutput| calls.borrow_mut().push(output))];
+
+        let reply = TestOutput {
+            id: Some(1),
+            value: 99,
+        };
+        if let Some(output) = claim_correlated_reply::<TestWorker>(reply.clone(), &pending) {
+            for subscriber in &subscribers {
+                subscriber(output.clone());
+            }
+        }
+
+        assert!(other_subscriber_calls.borrow().is_empty());
+        assert_eq!(rx.try_recv(), Ok(Some(reply)));
+    }
+}